@@ -0,0 +1,106 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT license.
+
+//! Shared proto-compilation helpers for Chariott's `build.rs` scripts.
+//!
+//! Every component that hosts a gRPC service repeats the same
+//! `tonic_build::configure()` boilerplate, including hardcoded relative
+//! paths to the shared proto root. Centralizing it here means a crate only
+//! has to list the `.proto` files it owns, and moving a crate around the
+//! workspace tree doesn't break anyone's includes.
+//!
+//! Compilation errors are surfaced as [`miette::Report`]s: `protox`'s parser
+//! diagnostics carry the offending file and span, so a bad `.proto` edit
+//! prints a caret at the undefined symbol or syntax error instead of an
+//! opaque `Box<dyn Error>` message.
+//!
+//! Before compiling, each file is also run through a small style [`lint`]
+//! pass so the proto surface stays consistent as more services are added.
+
+pub mod lint;
+
+use std::{
+    collections::HashSet,
+    env,
+    path::{Path, PathBuf},
+};
+
+use miette::IntoDiagnostic;
+use prost::Message;
+use tonic_build::configure;
+
+use lint::Violation;
+
+/// Compiles the given `.proto` files with the shared `chariott.common.v1`
+/// extern mapping and include paths already wired up, writing a
+/// `FileDescriptorSet` alongside the generated code so callers can register
+/// `tonic_reflection` without any extra setup.
+///
+/// `proto_paths` are resolved relative to the caller's `CARGO_MANIFEST_DIR`.
+/// The shared proto root used as an include path is found by walking up from
+/// that directory, so callers don't pass it themselves.
+pub fn compile_chariott_protos(proto_paths: &[&str]) -> miette::Result<()> {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").into_diagnostic()?);
+    let proto_root = workspace_proto_root(&manifest_dir);
+    let out_dir = PathBuf::from(env::var("OUT_DIR").into_diagnostic()?);
+
+    let files: Vec<PathBuf> = proto_paths.iter().map(|path| manifest_dir.join(path)).collect();
+
+    // A file not nested under the shared proto root (e.g. the detection
+    // example, which brings its own dedicated include directory) resolves
+    // to a bare filename rather than a package-shaped relative path, so the
+    // lint's package/directory check can't be applied to it.
+    let unrooted_from_shared_root: HashSet<String> = files
+        .iter()
+        .filter(|file| !file.starts_with(&proto_root))
+        .filter_map(|file| file.file_name().map(|name| name.to_string_lossy().into_owned()))
+        .collect();
+
+    let mut includes = vec![proto_root];
+    includes.extend(files.iter().filter_map(|file| file.parent().map(Path::to_path_buf)));
+
+    // `protox::Error` implements `miette::Diagnostic`, so `?` renders the
+    // parser's file/line/column and a caret at the bad syntax or symbol.
+    // Source info is requested so the lint pass below can report the real
+    // line a violation came from instead of always pointing at line 1.
+    let mut compiler = protox::Compiler::new(&includes)?;
+    compiler.include_source_info(true);
+    for file in &files {
+        compiler.open_file(file)?;
+    }
+    let descriptor_set = compiler.file_descriptor_set();
+
+    let violations = lint::lint(&descriptor_set, &unrooted_from_shared_root);
+    if !violations.is_empty() {
+        let report = violations.iter().map(Violation::to_string).collect::<Vec<_>>().join("\n");
+        return Err(miette::miette!("proto lint failed:\n{report}"));
+    }
+
+    let descriptor_name = files
+        .first()
+        .and_then(|file| file.file_stem())
+        .map(|stem| format!("{}_descriptor.bin", stem.to_string_lossy()))
+        .ok_or_else(|| miette::miette!("compile_chariott_protos requires at least one proto file"))?;
+    let descriptor_path = out_dir.join(descriptor_name);
+    std::fs::write(&descriptor_path, descriptor_set.encode_to_vec()).into_diagnostic()?;
+
+    configure()
+        .extern_path(".chariott.common.v1", "::chariott_common::proto::common")
+        .file_descriptor_set_path(&descriptor_path)
+        .skip_protoc_run()
+        .compile(&files, &includes)
+        .into_diagnostic()?;
+
+    Ok(())
+}
+
+/// Walks up from a crate's manifest directory to the workspace root and
+/// returns the path to the shared `proto/` directory, so crates that move
+/// within the tree don't need to update a relative `../../proto/` path.
+fn workspace_proto_root(manifest_dir: &Path) -> PathBuf {
+    manifest_dir
+        .ancestors()
+        .find(|ancestor| ancestor.join("proto").is_dir())
+        .map(|ancestor| ancestor.join("proto"))
+        .unwrap_or_else(|| manifest_dir.join("../../proto"))
+}
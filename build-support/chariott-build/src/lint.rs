@@ -0,0 +1,363 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT license.
+
+//! A small "tidy" pass for the `.proto` files this crate compiles.
+//!
+//! This is not a general-purpose proto linter: it only enforces the
+//! conventions Chariott's own protos are expected to follow, so that the
+//! surface stays consistent as more services are added. It runs before
+//! compilation in [`crate::compile_chariott_protos`] and fails the build if
+//! any violation is found, reporting every violation at once.
+
+use std::{collections::HashSet, path::Path};
+
+use prost_types::{
+    field_descriptor_proto::Type, DescriptorProto, EnumDescriptorProto, FileDescriptorProto,
+    FileDescriptorSet,
+};
+
+// Field numbers from `descriptor.proto`, used to look up a `SourceCodeInfo`
+// location for the construct at a given path. These are part of protobuf's
+// stable wire format, not something Chariott's protos define.
+const FILE_PACKAGE: i32 = 2;
+const FILE_MESSAGE_TYPE: i32 = 4;
+const FILE_ENUM_TYPE: i32 = 5;
+const FILE_SYNTAX: i32 = 12;
+const MESSAGE_FIELD: i32 = 2;
+const MESSAGE_NESTED_TYPE: i32 = 3;
+const MESSAGE_ENUM_TYPE: i32 = 4;
+
+/// A single style violation, reported with the file and line it came from
+/// so it reads like a compiler diagnostic. The line comes from the proto's
+/// `SourceCodeInfo`, so it's only as precise as the span `protox` recorded
+/// for that construct.
+#[derive(Debug)]
+pub struct Violation {
+    pub file: String,
+    pub line: u32,
+    pub message: String,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+/// Checks every file in `descriptor_set` against Chariott's proto
+/// conventions and returns one [`Violation`] per offence, in file order.
+///
+/// `descriptor_set` must have been compiled with source info included (see
+/// `protox::Compiler::include_source_info`), otherwise every violation is
+/// reported at line 1.
+///
+/// `unrooted_from_shared_root` lists (by `file.name()`, e.g.
+/// `"detection.proto"`) files that `protox` resolved against their own
+/// dedicated include directory rather than the shared proto root — for
+/// those, `file.name()` is just the bare filename and carries no directory
+/// to check the package against, so the package/directory convention is
+/// skipped rather than flagged as a mismatch.
+pub fn lint(
+    descriptor_set: &FileDescriptorSet,
+    unrooted_from_shared_root: &HashSet<String>,
+) -> Vec<Violation> {
+    descriptor_set
+        .file
+        .iter()
+        .flat_map(|file| lint_file(file, unrooted_from_shared_root))
+        .collect()
+}
+
+fn lint_file(file: &FileDescriptorProto, unrooted_from_shared_root: &HashSet<String>) -> Vec<Violation> {
+    let name = file.name().to_owned();
+    let mut violations = Vec::new();
+
+    if file.syntax.as_deref() != Some("proto3") {
+        violations.push(violation(
+            &name,
+            line(file, &[FILE_SYNTAX]),
+            "missing `syntax = \"proto3\";` declaration".to_owned(),
+        ));
+    }
+
+    if let Some(package) = file.package.as_deref() {
+        if unrooted_from_shared_root.contains(&name) {
+            // This file was compiled from its own dedicated include
+            // directory rather than the shared proto root (e.g. the
+            // detection example), so `file.name()` is just the bare
+            // filename and has no directory left to compare against the
+            // package. There's nothing meaningful to check here.
+        } else {
+            // `file.name()` is relative to the shared proto root in this
+            // case, so the package's directory is compared against that
+            // relative path directly.
+            let expected_dir = package.replace('.', "/");
+            let actual_dir = Path::new(&name).parent().unwrap_or_else(|| Path::new(""));
+            if actual_dir != Path::new(&expected_dir) {
+                violations.push(violation(
+                    &name,
+                    line(file, &[FILE_PACKAGE]),
+                    format!("package `{package}` must live under `{expected_dir}/`"),
+                ));
+            }
+        }
+    }
+
+    for (index, message) in file.message_type.iter().enumerate() {
+        lint_message(message, file, &[FILE_MESSAGE_TYPE, index as i32], &mut violations);
+    }
+    for (index, enum_type) in file.enum_type.iter().enumerate() {
+        lint_enum(enum_type, file, &[FILE_ENUM_TYPE, index as i32], &mut violations);
+    }
+
+    violations
+}
+
+fn lint_message(
+    message: &DescriptorProto,
+    file: &FileDescriptorProto,
+    path: &[i32],
+    violations: &mut Vec<Violation>,
+) {
+    let name = message.name();
+    if !is_pascal_case(name) {
+        violations.push(violation(
+            file.name(),
+            line(file, path),
+            format!("message `{name}` must be PascalCase"),
+        ));
+    }
+
+    let mut reserved_numbers = Vec::new();
+    for range in &message.reserved_range {
+        if let (Some(start), Some(end)) = (range.start, range.end) {
+            reserved_numbers.extend(start..end);
+        }
+    }
+
+    for (index, field) in message.field.iter().enumerate() {
+        let field_path = [path, &[MESSAGE_FIELD, index as i32]].concat();
+        let field_name = field.name();
+        if !is_snake_case(field_name) {
+            violations.push(violation(
+                file.name(),
+                line(file, &field_path),
+                format!("field `{name}.{field_name}` must be snake_case"),
+            ));
+        }
+        if field.r#type() != Type::Group {
+            if let Some(number) = field.number {
+                if reserved_numbers.contains(&number) {
+                    violations.push(violation(
+                        file.name(),
+                        line(file, &field_path),
+                        format!("field `{name}.{field_name}` reuses reserved tag {number}"),
+                    ));
+                }
+            }
+        }
+    }
+
+    for (index, nested) in message.nested_type.iter().enumerate() {
+        let nested_path = [path, &[MESSAGE_NESTED_TYPE, index as i32]].concat();
+        lint_message(nested, file, &nested_path, violations);
+    }
+    for (index, nested_enum) in message.enum_type.iter().enumerate() {
+        let nested_path = [path, &[MESSAGE_ENUM_TYPE, index as i32]].concat();
+        lint_enum(nested_enum, file, &nested_path, violations);
+    }
+}
+
+fn lint_enum(
+    enum_type: &EnumDescriptorProto,
+    file: &FileDescriptorProto,
+    path: &[i32],
+    violations: &mut Vec<Violation>,
+) {
+    let name = enum_type.name();
+    if !is_pascal_case(name) {
+        violations.push(violation(
+            file.name(),
+            line(file, path),
+            format!("enum `{name}` must be PascalCase"),
+        ));
+    }
+}
+
+fn violation(file: &str, line: u32, message: String) -> Violation {
+    Violation { file: file.to_owned(), line, message }
+}
+
+/// Looks up the 1-indexed source line for the construct at `path` in
+/// `file`'s `SourceCodeInfo`, falling back to line 1 if the file was
+/// compiled without source info or has no location recorded for it.
+fn line(file: &FileDescriptorProto, path: &[i32]) -> u32 {
+    file.source_code_info
+        .as_ref()
+        .and_then(|info| info.location.iter().find(|location| location.path == path))
+        .and_then(|location| location.span.first())
+        .map(|start_line| *start_line as u32 + 1)
+        .unwrap_or(1)
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_uppercase)
+        && !name.contains('_')
+        && name.chars().all(char::is_alphanumeric)
+}
+
+fn is_snake_case(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().next().is_some_and(|c| c.is_lowercase() || c == '_')
+        && name.chars().all(|c| c.is_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use prost_types::{
+        descriptor_proto::ReservedRange, DescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, FileDescriptorSet,
+    };
+
+    use super::*;
+
+    fn file(name: &str, package: &str) -> FileDescriptorProto {
+        FileDescriptorProto {
+            name: Some(name.to_owned()),
+            package: Some(package.to_owned()),
+            syntax: Some("proto3".to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn fixtures_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+    }
+
+    /// Compiles `proto_path` exactly the way `compile_chariott_protos` does:
+    /// the shared proto root listed first, then the file's own directory —
+    /// so a file that isn't nested under the shared root (like the
+    /// detection example) still resolves via its own include.
+    fn compile_with_real_protox(proto_path: &Path) -> FileDescriptorSet {
+        let proto_root = fixtures_dir().join("proto");
+        let files = [proto_path.to_path_buf()];
+        let mut includes = vec![proto_root];
+        includes.extend(files.iter().filter_map(|f| f.parent().map(Path::to_path_buf)));
+
+        let mut compiler = protox::Compiler::new(&includes).unwrap();
+        compiler.include_source_info(true);
+        compiler.open_file(proto_path).unwrap();
+        compiler.file_descriptor_set()
+    }
+
+    #[test]
+    fn is_pascal_case_accepts_and_rejects() {
+        assert!(is_pascal_case("StreamRequest"));
+        assert!(!is_pascal_case("streamRequest"));
+        assert!(!is_pascal_case("Stream_Request"));
+        assert!(!is_pascal_case(""));
+    }
+
+    #[test]
+    fn is_snake_case_accepts_and_rejects() {
+        assert!(is_snake_case("stream_id"));
+        assert!(is_snake_case("id"));
+        assert!(!is_snake_case("streamId"));
+        assert!(!is_snake_case("StreamId"));
+        assert!(!is_snake_case(""));
+    }
+
+    #[test]
+    fn flags_missing_proto3_syntax() {
+        let mut f = file("chariott/streaming/v1/streaming.proto", "chariott.streaming.v1");
+        f.syntax = None;
+
+        let violations = lint(&FileDescriptorSet { file: vec![f] }, &HashSet::new());
+
+        assert!(violations.iter().any(|v| v.message.contains("proto3")));
+    }
+
+    #[test]
+    fn flags_package_directory_mismatch() {
+        let f = file("chariott/streaming/v1/streaming.proto", "chariott.streaming.v2");
+
+        let violations = lint(&FileDescriptorSet { file: vec![f] }, &HashSet::new());
+
+        assert!(violations.iter().any(|v| v.message.contains("must live under")));
+    }
+
+    #[test]
+    fn flags_non_pascal_case_message() {
+        let mut f = file("chariott/streaming/v1/streaming.proto", "chariott.streaming.v1");
+        f.message_type
+            .push(DescriptorProto { name: Some("stream_request".to_owned()), ..Default::default() });
+
+        let violations = lint(&FileDescriptorSet { file: vec![f] }, &HashSet::new());
+
+        assert!(violations.iter().any(|v| v.message.contains("PascalCase")));
+    }
+
+    #[test]
+    fn flags_field_reusing_a_reserved_tag() {
+        let mut f = file("chariott/streaming/v1/streaming.proto", "chariott.streaming.v1");
+        f.message_type.push(DescriptorProto {
+            name: Some("StreamRequest".to_owned()),
+            field: vec![FieldDescriptorProto {
+                name: Some("stream_id".to_owned()),
+                number: Some(2),
+                ..Default::default()
+            }],
+            reserved_range: vec![ReservedRange { start: Some(2), end: Some(3) }],
+            ..Default::default()
+        });
+
+        let violations = lint(&FileDescriptorSet { file: vec![f] }, &HashSet::new());
+
+        assert!(violations.iter().any(|v| v.message.contains("reuses reserved tag")));
+    }
+
+    #[test]
+    fn accepts_a_real_file_nested_under_the_shared_proto_root() {
+        let proto_path = fixtures_dir().join("proto/chariott/streaming/v1/streaming.proto");
+        let descriptor_set = compile_with_real_protox(&proto_path);
+
+        let violations = lint(&descriptor_set, &HashSet::new());
+
+        assert!(violations.is_empty(), "unexpected violations: {violations:?}");
+    }
+
+    #[test]
+    fn skips_the_directory_check_for_a_real_file_resolved_from_its_own_include_dir() {
+        // Regression test for the bug where this crate's own detection
+        // example got flagged on every build: `protox` resolves a file
+        // included from its own dedicated directory (not nested under the
+        // shared proto root) to a bare filename with no package-shaped
+        // directory in it at all, so there's nothing to check here.
+        let proto_path = fixtures_dir().join("detection/v1/detection.proto");
+        let descriptor_set = compile_with_real_protox(&proto_path);
+        assert_eq!(descriptor_set.file.last().unwrap().name(), "detection.proto");
+
+        let unrooted = HashSet::from(["detection.proto".to_owned()]);
+        let violations = lint(&descriptor_set, &unrooted);
+
+        assert!(
+            !violations.iter().any(|v| v.message.contains("must live under")),
+            "unexpected violations: {violations:?}"
+        );
+    }
+
+    #[test]
+    fn still_catches_other_violations_in_a_file_resolved_from_its_own_include_dir() {
+        let proto_path = fixtures_dir().join("detection/v1/detection.proto");
+        let mut descriptor_set = compile_with_real_protox(&proto_path);
+        descriptor_set.file.last_mut().unwrap().message_type[0].name =
+            Some("detection_request".to_owned());
+
+        let unrooted = HashSet::from(["detection.proto".to_owned()]);
+        let violations = lint(&descriptor_set, &unrooted);
+
+        assert!(violations.iter().any(|v| v.message.contains("PascalCase")));
+    }
+}
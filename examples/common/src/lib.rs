@@ -0,0 +1,22 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT license.
+
+/// Generated client/server code and the encoded `FileDescriptorSet` for the
+/// streaming service, so that binaries hosting it can register a
+/// `tonic_reflection` reflection service without shipping `streaming.proto`
+/// separately.
+pub mod streaming {
+    include!(concat!(env!("OUT_DIR"), "/chariott.streaming.v1.rs"));
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        include_bytes!(concat!(env!("OUT_DIR"), "/streaming_descriptor.bin"));
+}
+
+/// Generated client/server code and the encoded `FileDescriptorSet` for the
+/// detection example service.
+pub mod detection {
+    include!(concat!(env!("OUT_DIR"), "/chariott.examples.detection.v1.rs"));
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        include_bytes!(concat!(env!("OUT_DIR"), "/detection_descriptor.bin"));
+}